@@ -1,5 +1,15 @@
 // Property-Based Tests for Escrow Smart Contract
 // These tests verify correctness properties that should hold across all valid inputs
+//
+// Several properties deliberately restate invariants (e.g. `x >= 0` on unsigned values)
+// to keep each property self-describing, so the related lints are allowed here.
+#![allow(
+    unused_comparisons,
+    clippy::absurd_extreme_comparisons,
+    clippy::manual_abs_diff,
+    clippy::manual_checked_ops,
+    clippy::manual_range_contains
+)]
 
 #[cfg(test)]
 mod escrow_properties {
@@ -1359,6 +1369,7 @@ mod pool_escrow_properties {
 
     const BPS_DENOMINATOR: u64 = 10_000;
     const MIN_ESCROW_AMOUNT: u64 = 1_000_000; // 1 USDC
+    #[allow(dead_code)]
     const MAX_POOL_WORKERS: u64 = 10_000;
 
     // ========================================================================
@@ -57,57 +57,83 @@ mod fee_tests {
 }
 
 
-/// Test status transition validity
+/// Test status transition validity against the program's own transition table
 #[cfg(test)]
 mod status_tests {
-    use proptest::prelude::*;
-
-    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-    enum EscrowStatus {
-        Created,
-        Funded,
-        Released,
-        Refunded,
-        Frozen,
-    }
+    use zaphwork::{
+        pool_transition, transition, EscrowAction, EscrowStatus, PoolEscrowAction, PoolEscrowStatus,
+    };
 
     fn can_transition(from: EscrowStatus, to: EscrowStatus) -> bool {
-        match (from, to) {
-            // Created can only go to Funded (via fund) or be cancelled
-            (EscrowStatus::Created, EscrowStatus::Funded) => true,
-            // Funded can go to Released, Refunded, or Frozen
-            (EscrowStatus::Funded, EscrowStatus::Released) => true,
-            (EscrowStatus::Funded, EscrowStatus::Refunded) => true,
-            (EscrowStatus::Funded, EscrowStatus::Frozen) => true,
-            // Frozen can go to Released or Refunded (admin action)
-            (EscrowStatus::Frozen, EscrowStatus::Released) => true,
-            (EscrowStatus::Frozen, EscrowStatus::Refunded) => true,
-            // No other transitions allowed
-            _ => false,
-        }
+        let actions = [
+            EscrowAction::Fund,
+            EscrowAction::SubmitWork,
+            EscrowAction::RequestRevision,
+            EscrowAction::Resubmit,
+            EscrowAction::Approve,
+            EscrowAction::Settle,
+            EscrowAction::Refund,
+            EscrowAction::CancelUnacknowledged,
+            EscrowAction::Freeze,
+            EscrowAction::ExecuteVerdict { refund: true },
+            EscrowAction::ExecuteVerdict { refund: false },
+            EscrowAction::AutoRelease,
+            EscrowAction::WorkerRefund,
+            EscrowAction::ClaimPayout { last: false },
+            EscrowAction::ClaimPayout { last: true },
+        ];
+        actions.iter().any(|&action| transition(from, action).ok() == Some(to))
     }
 
     #[test]
     fn test_valid_transitions() {
         assert!(can_transition(EscrowStatus::Created, EscrowStatus::Funded));
-        assert!(can_transition(EscrowStatus::Funded, EscrowStatus::Released));
+        assert!(can_transition(EscrowStatus::Funded, EscrowStatus::Settled));
         assert!(can_transition(EscrowStatus::Funded, EscrowStatus::Refunded));
         assert!(can_transition(EscrowStatus::Funded, EscrowStatus::Frozen));
-        assert!(can_transition(EscrowStatus::Frozen, EscrowStatus::Released));
+        assert!(can_transition(EscrowStatus::Frozen, EscrowStatus::Settled));
         assert!(can_transition(EscrowStatus::Frozen, EscrowStatus::Refunded));
+        // Review cycle
+        assert!(can_transition(EscrowStatus::Funded, EscrowStatus::Submitted));
+        assert!(can_transition(EscrowStatus::Submitted, EscrowStatus::RevisionRequested));
+        assert!(can_transition(EscrowStatus::RevisionRequested, EscrowStatus::Submitted));
+        assert!(can_transition(EscrowStatus::Submitted, EscrowStatus::Approved));
+        assert!(can_transition(EscrowStatus::Approved, EscrowStatus::Settled));
+        assert!(can_transition(EscrowStatus::Approved, EscrowStatus::Claiming));
+        assert!(can_transition(EscrowStatus::Claiming, EscrowStatus::Settled));
     }
 
     #[test]
     fn test_invalid_transitions() {
         // Cannot go backwards
         assert!(!can_transition(EscrowStatus::Funded, EscrowStatus::Created));
-        assert!(!can_transition(EscrowStatus::Released, EscrowStatus::Funded));
+        assert!(!can_transition(EscrowStatus::Settled, EscrowStatus::Funded));
         // Cannot skip states
-        assert!(!can_transition(EscrowStatus::Created, EscrowStatus::Released));
+        assert!(!can_transition(EscrowStatus::Created, EscrowStatus::Settled));
         assert!(!can_transition(EscrowStatus::Created, EscrowStatus::Frozen));
         // Terminal states cannot transition
-        assert!(!can_transition(EscrowStatus::Released, EscrowStatus::Refunded));
-        assert!(!can_transition(EscrowStatus::Refunded, EscrowStatus::Released));
+        assert!(!can_transition(EscrowStatus::Settled, EscrowStatus::Refunded));
+        assert!(!can_transition(EscrowStatus::Refunded, EscrowStatus::Settled));
+        // Submitted work cannot be refunded without a dispute
+        assert!(!can_transition(EscrowStatus::Submitted, EscrowStatus::Refunded));
+    }
+
+    #[test]
+    fn test_pool_transitions() {
+        assert_eq!(
+            pool_transition(PoolEscrowStatus::Created, PoolEscrowAction::Fund).ok(),
+            Some(PoolEscrowStatus::Funded)
+        );
+        assert_eq!(
+            pool_transition(PoolEscrowStatus::Funded, PoolEscrowAction::Release).ok(),
+            Some(PoolEscrowStatus::Active)
+        );
+        assert_eq!(
+            pool_transition(PoolEscrowStatus::Active, PoolEscrowAction::Close).ok(),
+            Some(PoolEscrowStatus::Closed)
+        );
+        assert!(pool_transition(PoolEscrowStatus::Created, PoolEscrowAction::Release).is_err());
+        assert!(pool_transition(PoolEscrowStatus::Closed, PoolEscrowAction::Close).is_err());
     }
 }
 
@@ -490,6 +516,7 @@ mod amount_edge_cases {
 mod dispute_tests {
     const BPS_DENOMINATOR: u64 = 10_000;
 
+    #[allow(dead_code)]
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     enum EscrowStatus {
         Created,
@@ -881,3 +908,23 @@ mod admin_transfer_tests {
         assert_eq!(result.unwrap_err(), "InvalidAdmin");
     }
 }
+
+/// Test multisig (Squads vault PDA) admin flow
+#[cfg(test)]
+mod multisig_admin_tests {
+    use anchor_lang::prelude::Pubkey;
+    use zaphwork::squads_vault_address;
+
+    #[test]
+    fn squads_vault_is_off_curve() {
+        let vault = squads_vault_address(&Pubkey::new_unique(), 0);
+        assert!(!vault.is_on_curve());
+    }
+
+    #[test]
+    fn squads_vault_differs_per_index() {
+        let multisig = Pubkey::new_unique();
+        assert_ne!(squads_vault_address(&multisig, 0), squads_vault_address(&multisig, 1));
+        assert_eq!(squads_vault_address(&multisig, 0), squads_vault_address(&multisig, 0));
+    }
+}